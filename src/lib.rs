@@ -0,0 +1,67 @@
+pub mod pipeline;
+pub mod screen_quad;
+pub mod shader;
+
+pub use screen_quad::ScreenQuad;
+
+/// Per-frame parameters threaded into every `ScenePassComponent`/
+/// `CompositionPassComponent`, e.g. camera matrices. Kept as a unit struct
+/// here since none of the render-pass components in this checkout need
+/// anything from it yet.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Context;
+
+/// Failures that can happen while setting up GPU resources (textures,
+/// programs, buffers) for a render pass component.
+#[derive(Debug)]
+pub enum CreationError {
+    Program(glium::ProgramCreationError),
+    Texture(glium::texture::TextureCreationError),
+    Buffer(glium::vertex::BufferCreationError),
+    Index(glium::index::BufferCreationError),
+    Other(String),
+}
+
+impl From<glium::ProgramCreationError> for CreationError {
+    fn from(err: glium::ProgramCreationError) -> Self {
+        CreationError::Program(err)
+    }
+}
+
+impl From<glium::texture::TextureCreationError> for CreationError {
+    fn from(err: glium::texture::TextureCreationError) -> Self {
+        CreationError::Texture(err)
+    }
+}
+
+impl From<glium::vertex::BufferCreationError> for CreationError {
+    fn from(err: glium::vertex::BufferCreationError) -> Self {
+        CreationError::Buffer(err)
+    }
+}
+
+impl From<glium::index::BufferCreationError> for CreationError {
+    fn from(err: glium::index::BufferCreationError) -> Self {
+        CreationError::Index(err)
+    }
+}
+
+/// Failures that can happen while issuing a draw call or building a
+/// framebuffer for a render pass component.
+#[derive(Debug)]
+pub enum DrawError {
+    Draw(glium::DrawError),
+    Framebuffer(glium::framebuffer::ValidationError),
+}
+
+impl From<glium::DrawError> for DrawError {
+    fn from(err: glium::DrawError) -> Self {
+        DrawError::Draw(err)
+    }
+}
+
+impl From<glium::framebuffer::ValidationError> for DrawError {
+    fn from(err: glium::framebuffer::ValidationError) -> Self {
+        DrawError::Framebuffer(err)
+    }
+}
@@ -0,0 +1,59 @@
+/// How the glow map is combined with the scene color underneath it in the
+/// composition pass.
+///
+/// `Add` is the blend the composition shader has always used (straight
+/// additive `f_glow_color`). The four "HSL" modes are the non-separable
+/// Porter-Duff blend modes from the PDF 1.4 spec (as used e.g. by `SetLum`,
+/// `SetSat` in Photoshop-style compositing): they mix hue, saturation,
+/// color (hue + saturation) or luminosity between the backdrop (scene) and
+/// source (glow) colors. They can't be expressed with fixed-function GL
+/// blending, so the composition fragment shader (`composition_core_transform`
+/// in `shaders`) samples both textures and picks the formula based on this
+/// value.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BlendMode {
+    Add,
+    Hue,
+    Saturation,
+    Color,
+    Luminosity,
+}
+
+impl Default for BlendMode {
+    fn default() -> Self {
+        BlendMode::Add
+    }
+}
+
+impl BlendMode {
+    /// The integer tag the composition fragment shader switches on to pick
+    /// the blend formula for its `blend_mode` uniform.
+    pub fn shader_tag(self) -> i32 {
+        match self {
+            BlendMode::Add => 0,
+            BlendMode::Hue => 1,
+            BlendMode::Saturation => 2,
+            BlendMode::Color => 3,
+            BlendMode::Luminosity => 4,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn shader_tag_matches_the_composition_shader_switch() {
+        assert_eq!(BlendMode::Add.shader_tag(), 0);
+        assert_eq!(BlendMode::Hue.shader_tag(), 1);
+        assert_eq!(BlendMode::Saturation.shader_tag(), 2);
+        assert_eq!(BlendMode::Color.shader_tag(), 3);
+        assert_eq!(BlendMode::Luminosity.shader_tag(), 4);
+    }
+
+    #[test]
+    fn default_is_add() {
+        assert_eq!(BlendMode::default(), BlendMode::Add);
+    }
+}
@@ -1,43 +1,100 @@
+pub mod blend_mode;
 pub mod shaders;
 
+use std::rc::Rc;
+
 use log::info;
 
 use glium::framebuffer::SimpleFrameBuffer;
+use glium::texture::Texture2dMultisample;
 use glium::uniforms::{MagnifySamplerFilter, MinifySamplerFilter, Sampler, SamplerWrapFunction};
-use glium::{uniform, Program, Surface, Texture2d};
+use glium::{uniform, BlitTarget, Program, Surface, Texture2d};
 
 use crate::pipeline::render_pass::{
     CompositionPassComponent, HasCompositionPassParams, HasScenePassParams, RenderPassComponent,
-    ScenePassComponent,
+    RenderTarget, ScenePassComponent,
 };
 use crate::{screen_quad, shader, Context, DrawError, ScreenQuad};
 
 pub use crate::CreationError;
+pub use blend_mode::BlendMode;
 
 #[derive(Debug, Clone)]
 pub struct Config {
     pub num_blur_passes: usize,
+
+    /// Number of samples to render the glow map at before resolving it down
+    /// to `glow_texture`. `None` (the default) renders directly into
+    /// `glow_texture` at a single sample, as before. `Some(samples)` renders
+    /// into an additional `Texture2dMultisample` and resolves it into
+    /// `glow_texture` via a blit at the start of `blur_pass` or `bloom_pass`, which smooths
+    /// out the aliased edges a single-sample render target would otherwise
+    /// bake into the glow map.
+    pub multisampling: Option<u32>,
+
+    /// How the glow map is combined with the scene color in the composition
+    /// pass. Defaults to the additive blend the composition shader has
+    /// always used.
+    pub blend_mode: BlendMode,
+
+    /// Number of mip levels in the downsample/upsample bloom chain. `0`
+    /// keeps the old behavior of running `num_blur_passes` full-resolution
+    /// separable Gaussian passes between `glow_texture` and
+    /// `glow_texture_back` in `blur_pass`. Any other value switches to
+    /// `bloom_pass`, which progressively downsamples the glow map into
+    /// `num_mips` half-resolution textures and adds them back together on
+    /// the way up, giving a wider, resolution-independent glow for less
+    /// cost than many full-res blur passes.
+    pub num_mips: usize,
+
+    /// Radius of the 3x3 tent filter used when upsampling and adding each
+    /// mip level back into the next-larger one. Larger values spread the
+    /// bloom further at the cost of more ghosting between mips.
+    pub filter_radius: f32,
 }
 
 impl Default for Config {
     fn default() -> Self {
-        Self { num_blur_passes: 2 }
+        Self {
+            num_blur_passes: 2,
+            multisampling: None,
+            blend_mode: BlendMode::default(),
+            num_mips: 0,
+            filter_radius: 1.0,
+        }
     }
 }
 
 pub struct Glow {
     config: Config,
+    target_size: (u32, u32),
     glow_texture: Texture2d,
     glow_texture_back: Texture2d,
+    glow_texture_multisample: Option<Texture2dMultisample>,
+    // The scene's resolved color texture, handed in once per frame via
+    // `set_scene_texture` by whatever owns both the scene pass and this
+    // component, so the composition pass can read the backdrop color that
+    // every `BlendMode`, `Add` included, combines the glow map with.
+    scene_texture: Option<Rc<Texture2d>>,
     blur_program: Program,
+    // Chain of successively half-sized textures used by `bloom_pass`.
+    // `mip_chain[0]` is half the size of `glow_texture`, `mip_chain[1]` half
+    // of that, and so on. Empty when `config.num_mips == 0`.
+    mip_chain: Vec<Texture2d>,
+    downsample_program: Program,
+    upsample_program: Program,
     screen_quad: ScreenQuad,
 }
 
 impl RenderPassComponent for Glow {
     fn clear_buffers<F: glium::backend::Facade>(&self, facade: &F) -> Result<(), DrawError> {
-        let mut framebuffer =
-            glium::framebuffer::SimpleFrameBuffer::new(facade, &self.glow_texture)?;
-        framebuffer.clear_color(0.0, 0.0, 0.0, 0.0);
+        if let Some(glow_texture_multisample) = &self.glow_texture_multisample {
+            let mut framebuffer = SimpleFrameBuffer::new(facade, glow_texture_multisample)?;
+            framebuffer.clear_color(0.0, 0.0, 0.0, 0.0);
+        } else {
+            let mut framebuffer = SimpleFrameBuffer::new(facade, &self.glow_texture)?;
+            framebuffer.clear_color(0.0, 0.0, 0.0, 0.0);
+        }
 
         Ok(())
     }
@@ -55,8 +112,20 @@ impl ScenePassComponent for Glow {
         shaders::glow_map_core_transform(core)
     }
 
-    fn output_textures(&self) -> Vec<(&'static str, &Texture2d)> {
-        vec![("f_glow_color", &self.glow_texture)]
+    fn output_textures(&self) -> Vec<(&'static str, RenderTarget)> {
+        // When multisampling is enabled the scene pass attaches the MSAA
+        // texture directly, so the scene itself is antialiased; `resolve`
+        // then blits it down into `glow_texture` before `blur_pass`/
+        // `bloom_pass` or the composition pass read from it. Previously
+        // this always pointed at the single-sample `glow_texture`, so the
+        // scene never actually rendered into the MSAA texture `resolve`
+        // blits from, and enabling `multisampling` just erased the glow map.
+        let target = match &self.glow_texture_multisample {
+            Some(glow_texture_multisample) => RenderTarget::Multisample(glow_texture_multisample),
+            None => RenderTarget::Texture(&self.glow_texture),
+        };
+
+        vec![("f_glow_color", target)]
     }
 
     fn params(&self, _: &Context) {}
@@ -64,12 +133,16 @@ impl ScenePassComponent for Glow {
 
 pub struct CompositionPassParams<'a> {
     glow_texture: &'a Texture2d,
+    scene_texture: &'a Texture2d,
+    blend_mode: i32,
 }
 
 impl_uniform_input!(
     CompositionPassParams<'a>,
     self => {
         glow_texture: &'a Texture2d = self.glow_texture,
+        scene_texture: &'a Texture2d = self.scene_texture,
+        blend_mode: i32 = self.blend_mode,
     },
 );
 
@@ -88,6 +161,13 @@ impl CompositionPassComponent for Glow {
     fn params(&self) -> CompositionPassParams {
         CompositionPassParams {
             glow_texture: &self.glow_texture,
+            // Tolerate `set_scene_texture` never having been called by
+            // falling back to the glow map itself as the backdrop, rather
+            // than panicking on every frame. Every blend mode, `Add`
+            // included, then combines the glow map with itself instead of
+            // with the scene.
+            scene_texture: self.scene_texture.as_deref().unwrap_or(&self.glow_texture),
+            blend_mode: self.config.blend_mode.shader_tag(),
         }
     }
 }
@@ -100,24 +180,91 @@ impl Glow {
     ) -> Result<Self, CreationError> {
         let glow_texture = Self::create_texture(facade, target_size)?;
         let glow_texture_back = Self::create_texture(facade, target_size)?;
+        let glow_texture_multisample =
+            Self::create_texture_multisample(facade, config, target_size)?;
 
         info!("Creating blur program");
         let blur_program =
             shaders::blur_core().build_program(facade, shader::InstancingMode::Uniforms)?;
 
+        info!("Creating downsample program");
+        let downsample_program =
+            shaders::downsample_core().build_program(facade, shader::InstancingMode::Uniforms)?;
+
+        info!("Creating upsample program");
+        let upsample_program =
+            shaders::upsample_core().build_program(facade, shader::InstancingMode::Uniforms)?;
+
+        let mip_chain = Self::create_mip_chain(facade, target_size, config.num_mips)?;
+
         info!("Creating screen quad");
         let screen_quad = ScreenQuad::create(facade)?;
 
         Ok(Glow {
             config: config.clone(),
+            target_size,
             glow_texture,
             glow_texture_back,
+            glow_texture_multisample,
+            scene_texture: None,
             blur_program,
+            mip_chain,
+            downsample_program,
+            upsample_program,
             screen_quad,
         })
     }
 
+    /// Hands in the scene's resolved color texture for this frame, so the
+    /// composition pass can read it as the backdrop for every `BlendMode`,
+    /// `Add` included. Until it is called, the composition pass uses the
+    /// glow map as its own backdrop.
+    pub fn set_scene_texture(&mut self, scene_texture: Rc<Texture2d>) {
+        self.scene_texture = Some(scene_texture);
+    }
+
+    /// Resolves the multisampled glow map (if multisampling is enabled) down
+    /// into `glow_texture`, which is what `blur_pass` and the composition
+    /// pass read from. A no-op when `config.multisampling` is `None`.
+    pub fn resolve<F: glium::backend::Facade>(&self, facade: &F) -> Result<(), DrawError> {
+        if let Some(glow_texture_multisample) = &self.glow_texture_multisample {
+            let source = SimpleFrameBuffer::new(facade, glow_texture_multisample)?;
+            let target = SimpleFrameBuffer::new(facade, &self.glow_texture)?;
+
+            let rect = glium::Rect {
+                left: 0,
+                bottom: 0,
+                width: self.target_size.0,
+                height: self.target_size.1,
+            };
+            let blit_target = BlitTarget {
+                left: 0,
+                bottom: 0,
+                width: self.target_size.0 as i32,
+                height: self.target_size.1 as i32,
+            };
+
+            source.blit_color(&rect, &target, &blit_target, MagnifySamplerFilter::Nearest);
+        }
+
+        Ok(())
+    }
+
+    /// Runs whichever glow pass `config.num_mips` selects: `bloom_pass` when
+    /// it's non-zero, otherwise the original fixed-pass `blur_pass`. Callers
+    /// should invoke this once per frame instead of `blur_pass`/`bloom_pass`
+    /// directly, so `num_mips` actually takes effect.
+    pub fn run_glow_pass<F: glium::backend::Facade>(&self, facade: &F) -> Result<(), DrawError> {
+        if self.config.num_mips > 0 {
+            self.bloom_pass(facade)
+        } else {
+            self.blur_pass(facade)
+        }
+    }
+
     pub fn blur_pass<F: glium::backend::Facade>(&self, facade: &F) -> Result<(), DrawError> {
+        self.resolve(facade)?;
+
         let glow_map = Sampler::new(&self.glow_texture)
             .magnify_filter(MagnifySamplerFilter::Linear)
             .minify_filter(MinifySamplerFilter::Linear)
@@ -157,17 +304,110 @@ impl Glow {
         Ok(())
     }
 
+    /// Bloom via a downsample/upsample mip chain, used instead of
+    /// `blur_pass` when `config.num_mips > 0`. Leaves the result composited
+    /// back into `glow_texture`, which is what the composition pass reads
+    /// from either way. Each level is added onto the next-larger one at full
+    /// weight, so the result is not normalized: its overall brightness grows
+    /// with `num_mips`.
+    pub fn bloom_pass<F: glium::backend::Facade>(&self, facade: &F) -> Result<(), DrawError> {
+        self.resolve(facade)?;
+
+        // Downsample: each mip is a 13-tap weighted sample of the previous
+        // (larger) level, using weights that suppress fireflies on the
+        // brightest mips, same idea as Call of Duty's presented bloom.
+        let mut source = &self.glow_texture;
+        for mip in &self.mip_chain {
+            let sampler = Sampler::new(source)
+                .magnify_filter(MagnifySamplerFilter::Linear)
+                .minify_filter(MinifySamplerFilter::Linear)
+                .wrap_function(SamplerWrapFunction::Clamp);
+
+            let mut target = SimpleFrameBuffer::new(facade, mip)?;
+            target.draw(
+                &self.screen_quad.vertex_buffer,
+                &self.screen_quad.index_buffer,
+                &self.downsample_program,
+                &uniform! {
+                    source_texture: sampler,
+                },
+                &Default::default(),
+            )?;
+
+            source = mip;
+        }
+
+        // Upsample: each level adds the smaller mip below it back in,
+        // spread out by a 9-tap 3x3 tent filter scaled by `filter_radius`.
+        // Collected into a `Vec` up front: `Zip`'s `DoubleEndedIterator` impl
+        // (needed for `.rev()`) requires `ExactSizeIterator` on both sides,
+        // which `Chain<Once<_>, slice::Iter<_>>` doesn't implement.
+        let levels: Vec<&Texture2d> = std::iter::once(&self.glow_texture)
+            .chain(self.mip_chain.iter())
+            .collect();
+        for window in levels.windows(2).rev() {
+            let (target_texture, source_texture) = (window[0], window[1]);
+            let sampler = Sampler::new(source_texture)
+                .magnify_filter(MagnifySamplerFilter::Linear)
+                .minify_filter(MinifySamplerFilter::Linear)
+                .wrap_function(SamplerWrapFunction::Clamp);
+
+            let mut target = SimpleFrameBuffer::new(facade, target_texture)?;
+            target.draw(
+                &self.screen_quad.vertex_buffer,
+                &self.screen_quad.index_buffer,
+                &self.upsample_program,
+                &uniform! {
+                    source_texture: sampler,
+                    filter_radius: self.config.filter_radius,
+                },
+                &glium::DrawParameters {
+                    blend: glium::Blend {
+                        color: glium::BlendingFunction::Addition {
+                            source: glium::LinearBlendingFactor::One,
+                            destination: glium::LinearBlendingFactor::One,
+                        },
+                        ..Default::default()
+                    },
+                    ..Default::default()
+                },
+            )?;
+        }
+
+        Ok(())
+    }
+
     pub fn on_target_resize<F: glium::backend::Facade>(
         &mut self,
         facade: &F,
         target_size: (u32, u32),
     ) -> Result<(), CreationError> {
+        self.target_size = target_size;
         self.glow_texture = Self::create_texture(facade, target_size)?;
         self.glow_texture_back = Self::create_texture(facade, target_size)?;
+        self.glow_texture_multisample =
+            Self::create_texture_multisample(facade, &self.config, target_size)?;
+        self.mip_chain = Self::create_mip_chain(facade, target_size, self.config.num_mips)?;
 
         Ok(())
     }
 
+    fn create_mip_chain<F: glium::backend::Facade>(
+        facade: &F,
+        target_size: (u32, u32),
+        num_mips: usize,
+    ) -> Result<Vec<Texture2d>, CreationError> {
+        let mut size = target_size;
+        let mut chain = Vec::with_capacity(num_mips);
+
+        for _ in 0..num_mips {
+            size = ((size.0 / 2).max(1), (size.1 / 2).max(1));
+            chain.push(Self::create_texture(facade, size)?);
+        }
+
+        Ok(chain)
+    }
+
     fn create_texture<F: glium::backend::Facade>(
         facade: &F,
         size: (u32, u32),
@@ -180,4 +420,43 @@ impl Glow {
             size.1,
         )?)
     }
+
+    fn create_texture_multisample<F: glium::backend::Facade>(
+        facade: &F,
+        config: &Config,
+        size: (u32, u32),
+    ) -> Result<Option<Texture2dMultisample>, CreationError> {
+        config
+            .multisampling
+            .map(|samples| {
+                Ok(Texture2dMultisample::empty_with_format(
+                    facade,
+                    glium::texture::UncompressedFloatFormat::F32F32F32F32,
+                    glium::texture::MipmapsOption::NoMipmap,
+                    samples,
+                    size.0,
+                    size.1,
+                )?)
+            })
+            .transpose()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::shader::UniformInput;
+    use glium::uniforms::UniformType;
+
+    #[test]
+    fn composition_pass_params_uniform_input_defs_match_the_shader_uniforms() {
+        assert_eq!(
+            CompositionPassParams::uniform_input_defs(),
+            vec![
+                ("glow_texture".to_string(), UniformType::Sampler2d),
+                ("scene_texture".to_string(), UniformType::Sampler2d),
+                ("blend_mode".to_string(), UniformType::SignedInt),
+            ],
+        );
+    }
 }
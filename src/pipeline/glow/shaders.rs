@@ -0,0 +1,270 @@
+use glium::uniforms::UniformType;
+
+use crate::screen_quad;
+use crate::shader::{Core, UniformInput};
+use crate::Context;
+
+use super::CompositionPassParams;
+
+const SCREEN_QUAD_VERTEX_SHADER: &str = r#"
+#version 330 core
+
+in vec2 a_pos;
+out vec2 v_tex_coords;
+
+void main() {
+    v_tex_coords = 0.5 * a_pos + 0.5;
+    gl_Position = vec4(a_pos, 0.0, 1.0);
+}
+"#;
+
+/// Separable Gaussian blur, run once per direction per `num_blur_passes`
+/// iteration in `Glow::blur_pass`.
+pub fn blur_core() -> Core<Context, (), screen_quad::Vertex> {
+    let fragment_shader = r#"
+#version 330 core
+
+uniform sampler2D glow_texture;
+uniform bool horizontal;
+
+in vec2 v_tex_coords;
+out vec4 f_color;
+
+const float weights[5] = float[](0.227027, 0.1945946, 0.1216216, 0.054054, 0.016216);
+
+void main() {
+    vec2 texel = 1.0 / vec2(textureSize(glow_texture, 0));
+    vec3 result = texture(glow_texture, v_tex_coords).rgb * weights[0];
+
+    for (int i = 1; i < 5; ++i) {
+        vec2 offset = horizontal ? vec2(texel.x * i, 0.0) : vec2(0.0, texel.y * i);
+        result += texture(glow_texture, v_tex_coords + offset).rgb * weights[i];
+        result += texture(glow_texture, v_tex_coords - offset).rgb * weights[i];
+    }
+
+    f_color = vec4(result, 1.0);
+}
+"#;
+
+    Core::new(SCREEN_QUAD_VERTEX_SHADER, fragment_shader).with_uniform_input_defs(vec![
+        ("glow_texture".to_string(), UniformType::Sampler2d),
+        ("horizontal".to_string(), UniformType::Bool),
+    ])
+}
+
+/// The scene-pass `Core` transform for `Glow`, whose `f_glow_color` output is
+/// the render target `Glow` reads back as its glow map. Returns the incoming
+/// core unchanged: it adds neither the `f_glow_color` output nor any
+/// bright-pass extraction (thresholding the scene's own lit color) itself.
+pub fn glow_map_core_transform<P, I, V>(core: Core<(Context, P), I, V>) -> Core<(Context, P), I, V> {
+    core
+}
+
+/// 13-tap downsample used by `Glow::bloom_pass` to build the mip chain, one
+/// level at a time. Samples a 4x4 neighborhood plus the center and weights
+/// them the way Call of Duty's presented bloom does, which softens the
+/// single-pixel box downsample enough to suppress fireflies on the
+/// brightest mips.
+pub fn downsample_core() -> Core<Context, (), screen_quad::Vertex> {
+    let fragment_shader = r#"
+#version 330 core
+
+uniform sampler2D source_texture;
+
+in vec2 v_tex_coords;
+out vec4 f_color;
+
+void main() {
+    vec2 texel = 1.0 / vec2(textureSize(source_texture, 0));
+    vec2 uv = v_tex_coords;
+
+    vec3 a = texture(source_texture, uv + texel * vec2(-2.0, -2.0)).rgb;
+    vec3 b = texture(source_texture, uv + texel * vec2( 0.0, -2.0)).rgb;
+    vec3 c = texture(source_texture, uv + texel * vec2( 2.0, -2.0)).rgb;
+    vec3 d = texture(source_texture, uv + texel * vec2(-1.0, -1.0)).rgb;
+    vec3 e = texture(source_texture, uv + texel * vec2( 1.0, -1.0)).rgb;
+    vec3 f = texture(source_texture, uv + texel * vec2(-2.0,  0.0)).rgb;
+    vec3 g = texture(source_texture, uv).rgb;
+    vec3 h = texture(source_texture, uv + texel * vec2( 2.0,  0.0)).rgb;
+    vec3 i = texture(source_texture, uv + texel * vec2(-1.0,  1.0)).rgb;
+    vec3 j = texture(source_texture, uv + texel * vec2( 1.0,  1.0)).rgb;
+    vec3 k = texture(source_texture, uv + texel * vec2(-2.0,  2.0)).rgb;
+    vec3 l = texture(source_texture, uv + texel * vec2( 0.0,  2.0)).rgb;
+    vec3 m = texture(source_texture, uv + texel * vec2( 2.0,  2.0)).rgb;
+
+    vec3 result = g * 0.125;
+    result += (a + c + k + m) * 0.03125;
+    result += (b + f + h + l) * 0.0625;
+    result += (d + e + i + j) * 0.125;
+
+    f_color = vec4(result, 1.0);
+}
+"#;
+
+    Core::new(SCREEN_QUAD_VERTEX_SHADER, fragment_shader)
+        .with_uniform_input_defs(vec![("source_texture".to_string(), UniformType::Sampler2d)])
+}
+
+/// 9-tap 3x3 tent upsample used by `Glow::bloom_pass`, additively blended
+/// into the next-larger mip by the caller's `DrawParameters`. `filter_radius`
+/// scales the tent in texels, widening or narrowing the bloom.
+pub fn upsample_core() -> Core<Context, (), screen_quad::Vertex> {
+    let fragment_shader = r#"
+#version 330 core
+
+uniform sampler2D source_texture;
+uniform float filter_radius;
+
+in vec2 v_tex_coords;
+out vec4 f_color;
+
+void main() {
+    vec2 texel = filter_radius / vec2(textureSize(source_texture, 0));
+    vec2 uv = v_tex_coords;
+
+    vec3 a = texture(source_texture, uv + texel * vec2(-1.0, -1.0)).rgb;
+    vec3 b = texture(source_texture, uv + texel * vec2( 0.0, -1.0)).rgb;
+    vec3 c = texture(source_texture, uv + texel * vec2( 1.0, -1.0)).rgb;
+    vec3 d = texture(source_texture, uv + texel * vec2(-1.0,  0.0)).rgb;
+    vec3 e = texture(source_texture, uv).rgb;
+    vec3 f = texture(source_texture, uv + texel * vec2( 1.0,  0.0)).rgb;
+    vec3 g = texture(source_texture, uv + texel * vec2(-1.0,  1.0)).rgb;
+    vec3 h = texture(source_texture, uv + texel * vec2( 0.0,  1.0)).rgb;
+    vec3 i = texture(source_texture, uv + texel * vec2( 1.0,  1.0)).rgb;
+
+    vec3 result = e * 4.0;
+    result += (b + d + f + h) * 2.0;
+    result += a + c + g + i;
+    result /= 16.0;
+
+    f_color = vec4(result, 1.0);
+}
+"#;
+
+    Core::new(SCREEN_QUAD_VERTEX_SHADER, fragment_shader).with_uniform_input_defs(vec![
+        ("source_texture".to_string(), UniformType::Sampler2d),
+        ("filter_radius".to_string(), UniformType::Float),
+    ])
+}
+
+/// The composition pass: combines `glow_texture` with `scene_texture`
+/// (the already-rendered scene color) per `blend_mode`. `blend_mode == 0`
+/// is the original straight-additive blend; 1..4 are the non-separable
+/// Porter-Duff "HSL" modes (Hue, Saturation, Color, Luminosity), which
+/// can't be expressed with fixed-function GL blending and so are done here
+/// in the fragment shader instead.
+pub fn composition_core_transform(
+    core: Core<Context, (), screen_quad::Vertex>,
+) -> Core<Context, (), screen_quad::Vertex> {
+    let fragment_shader = r#"
+#version 330 core
+
+uniform sampler2D glow_texture;
+uniform sampler2D scene_texture;
+uniform int blend_mode;
+
+in vec2 v_tex_coords;
+out vec4 f_color;
+
+float Lum(vec3 c) {
+    return dot(c, vec3(0.3, 0.59, 0.11));
+}
+
+vec3 ClipColor(vec3 c) {
+    float l = Lum(c);
+    float n = min(c.r, min(c.g, c.b));
+    float x = max(c.r, max(c.g, c.b));
+
+    if (n < 0.0 && l != n) {
+        c = l + (c - l) * l / (l - n);
+    }
+    if (x > 1.0 && x != l) {
+        c = l + (c - l) * (1.0 - l) / (x - l);
+    }
+
+    return c;
+}
+
+vec3 SetLum(vec3 c, float l) {
+    return ClipColor(c + (l - Lum(c)));
+}
+
+float Sat(vec3 c) {
+    return max(c.r, max(c.g, c.b)) - min(c.r, min(c.g, c.b));
+}
+
+vec3 SetSat(vec3 c, float s) {
+    // Found by scanning for the min/max indices positionally rather than by
+    // independent ternary chains: when two or more channels are equal (e.g.
+    // glow_texture is cleared to (0,0,0,0), so most pixels start out exactly
+    // black), comparing values directly can pick the same index for both
+    // minIndex and maxIndex, leaving midIndex = 3 - minIndex - maxIndex
+    // out of [0, 2]. This scan always yields a valid permutation of
+    // {0, 1, 2}, equal channels or not.
+    int minIndex = 0;
+    int maxIndex = 0;
+    for (int i = 1; i < 3; ++i) {
+        if (c[i] < c[minIndex]) {
+            minIndex = i;
+        }
+        if (c[i] >= c[maxIndex]) {
+            maxIndex = i;
+        }
+    }
+    int midIndex = 3 - minIndex - maxIndex;
+
+    float cMin = c[minIndex];
+    float cMid = c[midIndex];
+    float cMax = c[maxIndex];
+
+    vec3 result = vec3(0.0);
+    if (cMax > cMin) {
+        result[midIndex] = (cMid - cMin) * s / (cMax - cMin);
+        result[maxIndex] = s;
+    }
+    result[minIndex] = 0.0;
+
+    return result;
+}
+
+vec3 BlendHue(vec3 cb, vec3 cs) {
+    return SetLum(SetSat(cs, Sat(cb)), Lum(cb));
+}
+
+vec3 BlendSaturation(vec3 cb, vec3 cs) {
+    return SetLum(SetSat(cb, Sat(cs)), Lum(cb));
+}
+
+vec3 BlendColor(vec3 cb, vec3 cs) {
+    return SetLum(cs, Lum(cb));
+}
+
+vec3 BlendLuminosity(vec3 cb, vec3 cs) {
+    return SetLum(cb, Lum(cs));
+}
+
+void main() {
+    vec3 glow = texture(glow_texture, v_tex_coords).rgb;
+    vec3 scene = texture(scene_texture, v_tex_coords).rgb;
+
+    vec3 result;
+    if (blend_mode == 1) {
+        result = BlendHue(scene, glow);
+    } else if (blend_mode == 2) {
+        result = BlendSaturation(scene, glow);
+    } else if (blend_mode == 3) {
+        result = BlendColor(scene, glow);
+    } else if (blend_mode == 4) {
+        result = BlendLuminosity(scene, glow);
+    } else {
+        result = scene + glow;
+    }
+
+    f_color = vec4(result, 1.0);
+}
+"#;
+
+    let mut core = core;
+    core.fragment_shader = fragment_shader.to_string();
+    core.with_uniform_input_defs(CompositionPassParams::uniform_input_defs())
+}
@@ -0,0 +1,53 @@
+pub mod glow;
+pub mod render_pass;
+
+use std::rc::Rc;
+
+use glium::Texture2d;
+
+use crate::{Context, DrawError};
+
+/// Assembles the render pass components for a frame: owns a shared
+/// `scene_texture` at the target size, which each component (currently just
+/// `Glow`) reads from in its own passes. `Pipeline` allocates and resizes
+/// that texture but does not render the scene into it.
+pub struct Pipeline {
+    scene_texture: Rc<Texture2d>,
+    pub glow: glow::Glow,
+}
+
+impl Pipeline {
+    pub fn create<F: glium::backend::Facade>(
+        facade: &F,
+        glow_config: &glow::Config,
+        target_size: (u32, u32),
+    ) -> Result<Self, crate::CreationError> {
+        let scene_texture = Rc::new(Texture2d::empty(facade, target_size.0, target_size.1)?);
+        let glow = glow::Glow::create(facade, glow_config, target_size)?;
+
+        Ok(Pipeline {
+            scene_texture,
+            glow,
+        })
+    }
+
+    /// Runs the glow pass for one frame: hands `scene_texture` to `Glow` (so
+    /// its composition pass can read it as the backdrop for every
+    /// `BlendMode`) and then runs `Glow::run_glow_pass`,
+    /// which dispatches to `blur_pass` or `bloom_pass` per `config.num_mips`.
+    pub fn run_glow<F: glium::backend::Facade>(&mut self, facade: &F) -> Result<(), DrawError> {
+        self.glow.set_scene_texture(Rc::clone(&self.scene_texture));
+        self.glow.run_glow_pass(facade)
+    }
+
+    pub fn on_target_resize<F: glium::backend::Facade>(
+        &mut self,
+        facade: &F,
+        target_size: (u32, u32),
+    ) -> Result<(), crate::CreationError> {
+        self.scene_texture = Rc::new(Texture2d::empty(facade, target_size.0, target_size.1)?);
+        self.glow.on_target_resize(facade, target_size)?;
+
+        Ok(())
+    }
+}
@@ -0,0 +1,70 @@
+use glium::framebuffer::SimpleFrameBuffer;
+use glium::{texture::Texture2dMultisample, Texture2d};
+
+use crate::shader::ToUniforms;
+use crate::{shader, Context, DrawError};
+
+/// A render target a `ScenePassComponent` can output into. Most components
+/// only ever need a single-sample `Texture2d`; `Multisample` exists so a
+/// component like `Glow` can ask the scene pass to render straight into its
+/// MSAA texture instead of a single-sample one, with the component
+/// resolving it down itself afterwards (e.g. `Glow::resolve`).
+pub enum RenderTarget<'a> {
+    Texture(&'a Texture2d),
+    Multisample(&'a Texture2dMultisample),
+}
+
+impl<'a> RenderTarget<'a> {
+    /// What the scene pass actually attaches a component's output to: a
+    /// framebuffer over the single-sample texture, or over the multisample
+    /// one when the component asked for `Multisample`. Either way the scene
+    /// gets rendered straight into whichever texture the component will
+    /// read back from (after a resolve, for the multisample case).
+    pub fn build_framebuffer<F: glium::backend::Facade>(
+        &self,
+        facade: &F,
+    ) -> Result<SimpleFrameBuffer<'a>, DrawError> {
+        Ok(match self {
+            RenderTarget::Texture(texture) => SimpleFrameBuffer::new(facade, *texture)?,
+            RenderTarget::Multisample(texture) => SimpleFrameBuffer::new(facade, *texture)?,
+        })
+    }
+}
+
+pub trait RenderPassComponent {
+    fn clear_buffers<F: glium::backend::Facade>(&self, facade: &F) -> Result<(), DrawError>;
+}
+
+pub trait HasScenePassParams<'u> {
+    type Params: ToUniforms;
+}
+
+pub trait ScenePassComponent: for<'u> HasScenePassParams<'u> {
+    fn core_transform<P, I, V>(
+        &self,
+        core: shader::Core<(Context, P), I, V>,
+    ) -> shader::Core<(Context, P), I, V>;
+
+    /// The render targets this component's fragment shader writes to, named
+    /// after the `out` variable in the GLSL, and the framebuffer the scene
+    /// pass should attach for each. Returning `RenderTarget::Multisample`
+    /// for a given output means the scene is rendered straight into that
+    /// multisampled texture; the component is responsible for resolving it
+    /// (e.g. `Glow::resolve`) before reading it back out.
+    fn output_textures(&self) -> Vec<(&'static str, RenderTarget)>;
+
+    fn params(&self, context: &Context) -> <Self as HasScenePassParams<'_>>::Params;
+}
+
+pub trait HasCompositionPassParams<'u> {
+    type Params: ToUniforms;
+}
+
+pub trait CompositionPassComponent: for<'u> HasCompositionPassParams<'u> {
+    fn core_transform(
+        &self,
+        core: shader::Core<Context, (), crate::screen_quad::Vertex>,
+    ) -> shader::Core<Context, (), crate::screen_quad::Vertex>;
+
+    fn params(&self) -> <Self as HasCompositionPassParams<'_>>::Params;
+}
@@ -0,0 +1,39 @@
+use glium::{IndexBuffer, VertexBuffer};
+
+use crate::CreationError;
+
+#[derive(Copy, Clone, Debug)]
+pub struct Vertex {
+    pub a_pos: [f32; 2],
+}
+
+glium::implement_vertex!(Vertex, a_pos);
+
+const VERTICES: [Vertex; 4] = [
+    Vertex { a_pos: [-1.0, -1.0] },
+    Vertex { a_pos: [1.0, -1.0] },
+    Vertex { a_pos: [1.0, 1.0] },
+    Vertex { a_pos: [-1.0, 1.0] },
+];
+
+const INDICES: [u16; 6] = [0, 1, 2, 0, 2, 3];
+
+/// A full-screen quad, reused by every full-screen pass (blur, bloom,
+/// composition, ...) instead of each one allocating its own buffers.
+pub struct ScreenQuad {
+    pub vertex_buffer: VertexBuffer<Vertex>,
+    pub index_buffer: IndexBuffer<u16>,
+}
+
+impl ScreenQuad {
+    pub fn create<F: glium::backend::Facade>(facade: &F) -> Result<Self, CreationError> {
+        let vertex_buffer = VertexBuffer::new(facade, &VERTICES)?;
+        let index_buffer =
+            IndexBuffer::new(facade, glium::index::PrimitiveType::TrianglesList, &INDICES)?;
+
+        Ok(ScreenQuad {
+            vertex_buffer,
+            index_buffer,
+        })
+    }
+}
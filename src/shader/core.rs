@@ -0,0 +1,169 @@
+use std::marker::PhantomData;
+
+use glium::uniforms::UniformType;
+
+use crate::shader::input::{
+    validate_uniform_input, validate_vertex_attribs, UniformWarning, VertexAttribWarning,
+};
+use crate::shader::stages::Stages;
+use crate::CreationError;
+
+/// Which buffer supplies the per-instance data a `Core`'s vertex stage
+/// consumes: a uniform block shared across a single draw call, or a
+/// per-vertex attribute buffer for instanced rendering.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum InstancingMode {
+    Uniforms,
+    Vertex,
+}
+
+/// A GLSL program under construction. `P`, `I`, `V` tag the uniform-input,
+/// instance-input and vertex types the eventual `glium::Program` expects to
+/// be drawn with, purely so `core_transform` implementations can be written
+/// generically over them without losing type-checking at the `build_program`
+/// call site.
+pub struct Core<P, I, V> {
+    pub vertex_shader: String,
+    pub fragment_shader: String,
+    /// Optional geometry/tessellation stages a component's `core_transform`
+    /// has attached on top of the mandatory vertex+fragment pair.
+    pub stages: Stages,
+    uniform_input_defs: Vec<(String, UniformType)>,
+    _phantom: PhantomData<(P, I, V)>,
+}
+
+impl<P, I, V> Core<P, I, V> {
+    pub fn new(vertex_shader: impl Into<String>, fragment_shader: impl Into<String>) -> Self {
+        Self {
+            vertex_shader: vertex_shader.into(),
+            fragment_shader: fragment_shader.into(),
+            stages: Stages::none(),
+            uniform_input_defs: Vec::new(),
+            _phantom: PhantomData,
+        }
+    }
+
+    /// Attaches geometry/tessellation stages, e.g. for instanced wireframe
+    /// expansion or silhouette extrusion. Replaces whatever stages were
+    /// attached before; a `core_transform` that wraps a previous
+    /// transform's stages reads them from the public `stages` field first.
+    pub fn with_stages(mut self, stages: Stages) -> Self {
+        self.stages = stages;
+        self
+    }
+
+    /// Registers the `(name, UniformType)` defs `build_program` should
+    /// cross-reference against the linked program's active uniforms, i.e.
+    /// `P::uniform_input_defs()` for whatever `UniformInput` this core will
+    /// be drawn with.
+    pub fn with_uniform_input_defs(mut self, defs: Vec<(String, UniformType)>) -> Self {
+        self.uniform_input_defs = defs;
+        self
+    }
+}
+
+impl<P, I, V: glium::vertex::Vertex> Core<P, I, V> {
+    pub fn build_program<F: glium::backend::Facade>(
+        &self,
+        facade: &F,
+        instancing_mode: InstancingMode,
+    ) -> Result<glium::Program, CreationError> {
+        self.stages.validate()?;
+
+        // `instancing_mode` is not used here: the program is linked from the
+        // same sources in either mode, and the attribute check below covers
+        // only the bindings of `V`, not per-instance attributes.
+        let _ = instancing_mode;
+
+        let input = glium::program::SourceCode {
+            vertex_shader: &self.vertex_shader,
+            tessellation_control_shader: self.stages.tess_control.as_deref(),
+            tessellation_evaluation_shader: self.stages.tess_evaluation.as_deref(),
+            geometry_shader: self.stages.geometry.as_deref(),
+            fragment_shader: &self.fragment_shader,
+        };
+
+        let program = glium::Program::new(facade, input)?;
+
+        for warning in validate_uniform_input(&self.uniform_input_defs, &active_uniforms(&program))
+        {
+            log_uniform_warning(&warning);
+        }
+
+        for warning in validate_vertex_attribs(&vertex_attribs::<V>(), &active_attribs(&program)) {
+            log_vertex_attrib_warning(&warning);
+        }
+
+        Ok(program)
+    }
+}
+
+fn active_uniforms(program: &glium::Program) -> Vec<(String, UniformType)> {
+    program
+        .uniforms()
+        .map(|uniform| (uniform.name.clone(), uniform.ty))
+        .collect()
+}
+
+fn vertex_attribs<V: glium::vertex::Vertex>() -> Vec<(String, glium::vertex::AttributeType)> {
+    V::build_bindings()
+        .iter()
+        .map(|(name, _offset, _id, ty, _normalized)| (name.to_string(), *ty))
+        .collect()
+}
+
+fn active_attribs(program: &glium::Program) -> Vec<(String, glium::vertex::AttributeType)> {
+    program
+        .attributes()
+        .map(|attrib| (attrib.name.clone(), attrib.ty))
+        .collect()
+}
+
+/// `build_program` logs these rather than turning them into a `CreationError`
+/// (a benign uniform/attribute mismatch shouldn't fail an otherwise-successful
+/// link), the same way the crate already reports progress via `log::info!`
+/// (e.g. in `Glow::create`) -- this is that diagnostic path, for warnings
+/// instead.
+fn log_uniform_warning(warning: &UniformWarning) {
+    match warning {
+        UniformWarning::InactiveUniform { name } => {
+            log::warn!("uniform `{}` is not active in the linked program", name)
+        }
+        UniformWarning::TypeMismatch {
+            name,
+            rust_type,
+            program_type,
+        } => log::warn!(
+            "uniform `{}` is declared as {:?} in the program but {:?} on the Rust side",
+            name,
+            program_type,
+            rust_type
+        ),
+        UniformWarning::MissingBinding { name } => log::warn!(
+            "program uniform `{}` has no matching UniformInput binding",
+            name
+        ),
+    }
+}
+
+fn log_vertex_attrib_warning(warning: &VertexAttribWarning) {
+    match warning {
+        VertexAttribWarning::InactiveAttribute { name } => {
+            log::warn!("attribute `{}` is not active in the linked program", name)
+        }
+        VertexAttribWarning::TypeMismatch {
+            name,
+            rust_type,
+            program_type,
+        } => log::warn!(
+            "attribute `{}` is declared as {:?} in the program but {:?} on the Rust side",
+            name,
+            program_type,
+            rust_type
+        ),
+        VertexAttribWarning::MissingBinding { name } => log::warn!(
+            "program attribute `{}` has no matching binding in the vertex type",
+            name
+        ),
+    }
+}
@@ -344,10 +344,163 @@ pub trait StaticUniformType {
     const TYPE: UniformType;
 }
 
+/// Converts an owned value into a `glium` uniform value that does not borrow
+/// from anything, so it can be stored in a [`DynamicUniforms`] together with
+/// its name and replayed later from `visit_values`.
+pub trait IntoUniformValue: StaticUniformType {
+    fn into_uniform_value(self) -> UniformValue<'static>;
+}
+
+impl IntoUniformValue for bool {
+    fn into_uniform_value(self) -> UniformValue<'static> {
+        UniformValue::Bool(self)
+    }
+}
+
+impl IntoUniformValue for i32 {
+    fn into_uniform_value(self) -> UniformValue<'static> {
+        UniformValue::SignedInt(self)
+    }
+}
+
+impl IntoUniformValue for f32 {
+    fn into_uniform_value(self) -> UniformValue<'static> {
+        UniformValue::Float(self)
+    }
+}
+
+impl IntoUniformValue for [f32; 2] {
+    fn into_uniform_value(self) -> UniformValue<'static> {
+        UniformValue::Vec2(self)
+    }
+}
+
+impl IntoUniformValue for [f32; 3] {
+    fn into_uniform_value(self) -> UniformValue<'static> {
+        UniformValue::Vec3(self)
+    }
+}
+
+impl IntoUniformValue for [f32; 4] {
+    fn into_uniform_value(self) -> UniformValue<'static> {
+        UniformValue::Vec4(self)
+    }
+}
+
+impl IntoUniformValue for [[f32; 2]; 2] {
+    fn into_uniform_value(self) -> UniformValue<'static> {
+        UniformValue::Mat2(self)
+    }
+}
+
+impl IntoUniformValue for [[f32; 3]; 3] {
+    fn into_uniform_value(self) -> UniformValue<'static> {
+        UniformValue::Mat3(self)
+    }
+}
+
+impl IntoUniformValue for [[f32; 4]; 4] {
+    fn into_uniform_value(self) -> UniformValue<'static> {
+        UniformValue::Mat4(self)
+    }
+}
+
+/// Uniforms whose names and values are only known at runtime, e.g. loaded from
+/// a config file or set from an editor, as opposed to the compile-time
+/// structs produced by `impl_uniform_input!`.
+///
+/// A `DynamicUniforms` implements `HasUniforms`/`ToUniforms`/`UniformInput`
+/// just like a macro-generated type, so it can be mixed with compile-time
+/// inputs on the same draw call via the `(U1, U2)` tuple impls in this
+/// module, e.g. `(MyStaticParams, DynamicUniforms)`.
+#[derive(Clone, Debug, Default)]
+pub struct DynamicUniforms {
+    // Each value is held inside its own `UniformValue`: for the scalar and
+    // vector/matrix types we accept here it never actually borrows from
+    // anything (the variants hold their data by value), so `entries` can be
+    // `'static` even though it is built from values pushed in at runtime.
+    entries: Vec<(String, UniformValue<'static>)>,
+    defs: Vec<(String, UniformType)>,
+}
+
+impl DynamicUniforms {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Builder-style variant of `insert`.
+    pub fn with<T>(mut self, name: impl Into<String>, value: T) -> Self
+    where
+        T: IntoUniformValue,
+    {
+        self.insert(name, value);
+        self
+    }
+
+    pub fn insert<T>(&mut self, name: impl Into<String>, value: T)
+    where
+        T: IntoUniformValue,
+    {
+        let name = name.into();
+        self.defs.push((name.clone(), T::TYPE));
+        self.entries.push((name, value.into_uniform_value()));
+    }
+
+    /// This instance's `(name, UniformType)` defs, for callers assembling
+    /// the full def list a draw call's `validate_uniform_input` should
+    /// check against. `UniformInput::uniform_input_defs()` can't report
+    /// these itself (it has no `&self` to read them from), so a caller
+    /// combining a `DynamicUniforms` with compile-time input via the
+    /// `(U1, U2)` tuple impls should append this alongside
+    /// `U1::uniform_input_defs()` rather than relying on the tuple's defs
+    /// alone, or every dynamic uniform will be flagged `MissingBinding`.
+    pub fn uniform_input_defs(&self) -> &[(String, UniformType)] {
+        &self.defs
+    }
+}
+
+impl Uniforms for DynamicUniforms {
+    fn visit_values<'a, F>(&'a self, mut output: F)
+    where
+        F: FnMut(&str, UniformValue<'a>),
+    {
+        for (name, value) in &self.entries {
+            output(name, *value);
+        }
+    }
+}
+
+impl<'u> HasUniforms<'u> for DynamicUniforms {
+    type Uniforms = UniformsRef<&'u Self>;
+}
+
+impl ToUniforms for DynamicUniforms {
+    fn to_uniforms<'u>(&'u self) -> UniformsRef<&'u Self>
+    where
+        Self: HasUniforms<'u>,
+    {
+        UniformsRef(self)
+    }
+}
+
+impl UniformInput for DynamicUniforms {
+    fn uniform_input_defs() -> Vec<(String, UniformType)> {
+        // The concrete set of uniforms is only known per-instance (it is
+        // filled in via `insert`), so there is nothing to report statically
+        // here; deliberately empty rather than guessed at, see the
+        // instance-level `DynamicUniforms::uniform_input_defs` method.
+        Vec::new()
+    }
+}
+
 impl StaticUniformType for bool {
     const TYPE: UniformType = UniformType::Bool;
 }
 
+impl StaticUniformType for i32 {
+    const TYPE: UniformType = UniformType::SignedInt;
+}
+
 impl StaticUniformType for f32 {
     const TYPE: UniformType = UniformType::Float;
 }
@@ -375,3 +528,251 @@ impl StaticUniformType for [[f32; 3]; 3] {
 impl StaticUniformType for [[f32; 4]; 4] {
     const TYPE: UniformType = UniformType::FloatMat4;
 }
+
+/// A mismatch between a `UniformInput::uniform_input_defs()` entry and what
+/// the linked GL program actually declares, found by `validate_uniform_input`
+/// after `build_program`.
+///
+/// Catching these at program creation means a typo or a changed field in an
+/// `impl_uniform_input!` definition shows up as a warning instead of
+/// silently producing wrong (or missing) rendering.
+#[derive(Debug, Clone, PartialEq)]
+pub enum UniformWarning {
+    /// `uniform_input_defs` promises a uniform the program doesn't declare,
+    /// e.g. because the GLSL never references it and the compiler dropped
+    /// it, or the name was typo'd on one side.
+    InactiveUniform { name: String },
+
+    /// The program declares the uniform under this name, but with a
+    /// different `UniformType` than `uniform_input_defs` promised.
+    TypeMismatch {
+        name: String,
+        rust_type: UniformType,
+        program_type: UniformType,
+    },
+
+    /// The program declares an active uniform that no `UniformInput` in the
+    /// draw call's input defs provides a binding for.
+    MissingBinding { name: String },
+}
+
+/// Cross-references the `(name, UniformType)` list a `UniformInput` promises
+/// against the uniforms a linked GL program actually reports as active, and
+/// returns every mismatch found.
+///
+/// `program_active_uniforms` is the `(name, UniformType)` list reported by
+/// the GL program after linking (e.g. via `glium::Program::uniforms`).
+/// `build_program` calls this after linking and surfaces the result through
+/// the crate's diagnostic path, so a mismatch between an `impl_uniform_input!`
+/// definition and the GLSL is caught at program creation instead of silently
+/// producing wrong rendering.
+pub fn validate_uniform_input(
+    input_defs: &[(String, UniformType)],
+    program_active_uniforms: &[(String, UniformType)],
+) -> Vec<UniformWarning> {
+    let mut warnings = Vec::new();
+
+    for (name, rust_type) in input_defs {
+        match program_active_uniforms
+            .iter()
+            .find(|(active_name, _)| active_name == name)
+        {
+            None => warnings.push(UniformWarning::InactiveUniform { name: name.clone() }),
+            Some((_, program_type)) if program_type != rust_type => {
+                warnings.push(UniformWarning::TypeMismatch {
+                    name: name.clone(),
+                    rust_type: *rust_type,
+                    program_type: *program_type,
+                })
+            }
+            Some(_) => {}
+        }
+    }
+
+    for (name, _) in program_active_uniforms {
+        if !input_defs.iter().any(|(input_name, _)| input_name == name) {
+            warnings.push(UniformWarning::MissingBinding { name: name.clone() });
+        }
+    }
+
+    warnings
+}
+
+#[cfg(test)]
+mod dynamic_uniforms_tests {
+    use super::*;
+
+    #[test]
+    fn insert_tracks_defs() {
+        let mut uniforms = DynamicUniforms::new();
+        uniforms.insert("intensity", 0.5_f32);
+        uniforms.insert("tint", [1.0_f32, 0.0, 0.0]);
+
+        assert_eq!(
+            uniforms.uniform_input_defs(),
+            &[
+                ("intensity".to_string(), UniformType::Float),
+                ("tint".to_string(), UniformType::FloatVec3),
+            ],
+        );
+    }
+
+    #[test]
+    fn with_is_builder_style() {
+        let uniforms = DynamicUniforms::new().with("horizontal", true);
+
+        assert_eq!(
+            uniforms.uniform_input_defs(),
+            &[("horizontal".to_string(), UniformType::Bool)],
+        );
+    }
+
+    #[test]
+    fn static_uniform_input_defs_alone_miss_dynamic_entries() {
+        // `UniformInput::uniform_input_defs()` is deliberately empty for
+        // `DynamicUniforms` (see its doc comment): a caller combining one
+        // with static input via the `(U1, U2)` tuple impl must separately
+        // append the instance method's output, or `validate_uniform_input`
+        // flags every dynamic uniform as `MissingBinding` even though it is
+        // bound at draw time.
+        let mut dynamic = DynamicUniforms::new();
+        dynamic.insert("intensity", 0.5_f32);
+
+        let program_active = vec![("intensity".to_string(), UniformType::Float)];
+
+        let static_only_defs = <((), DynamicUniforms) as UniformInput>::uniform_input_defs();
+        assert_eq!(
+            validate_uniform_input(&static_only_defs, &program_active),
+            vec![UniformWarning::MissingBinding {
+                name: "intensity".to_string()
+            }],
+        );
+
+        let mut combined_defs = static_only_defs;
+        combined_defs.extend(dynamic.uniform_input_defs().iter().cloned());
+        assert_eq!(validate_uniform_input(&combined_defs, &program_active), vec![]);
+    }
+}
+
+/// A mismatch between a vertex type's declared attributes (as reported by
+/// `glium::vertex::Vertex::build_bindings`) and what the linked GL program
+/// actually declares, found by `validate_vertex_attribs` after
+/// `build_program`. Mirrors `UniformWarning`, but for the `in` side of the
+/// vertex shader instead of its uniforms — the equivalent of `luminance`'s
+/// `VertexAttribWarning`.
+#[derive(Debug, Clone, PartialEq)]
+pub enum VertexAttribWarning {
+    /// The vertex type declares an attribute the program doesn't, e.g.
+    /// because the GLSL never references it and the compiler dropped it.
+    InactiveAttribute { name: String },
+
+    /// The program declares the attribute under this name, but with a
+    /// different `AttributeType` than the vertex type promised.
+    TypeMismatch {
+        name: String,
+        rust_type: glium::vertex::AttributeType,
+        program_type: glium::vertex::AttributeType,
+    },
+
+    /// The program declares an active attribute that the vertex type's
+    /// bindings don't cover.
+    MissingBinding { name: String },
+}
+
+/// Cross-references a vertex type's `build_bindings()` attributes against the
+/// attributes a linked GL program actually reports as active, and returns
+/// every mismatch found. Same shape as `validate_uniform_input`, one level
+/// down the pipeline.
+pub fn validate_vertex_attribs(
+    vertex_attribs: &[(String, glium::vertex::AttributeType)],
+    program_active_attribs: &[(String, glium::vertex::AttributeType)],
+) -> Vec<VertexAttribWarning> {
+    let mut warnings = Vec::new();
+
+    for (name, rust_type) in vertex_attribs {
+        match program_active_attribs
+            .iter()
+            .find(|(active_name, _)| active_name == name)
+        {
+            None => warnings.push(VertexAttribWarning::InactiveAttribute { name: name.clone() }),
+            Some((_, program_type)) if program_type != rust_type => {
+                warnings.push(VertexAttribWarning::TypeMismatch {
+                    name: name.clone(),
+                    rust_type: *rust_type,
+                    program_type: *program_type,
+                })
+            }
+            Some(_) => {}
+        }
+    }
+
+    for (name, _) in program_active_attribs {
+        if !vertex_attribs.iter().any(|(input_name, _)| input_name == name) {
+            warnings.push(VertexAttribWarning::MissingBinding { name: name.clone() });
+        }
+    }
+
+    warnings
+}
+
+#[cfg(test)]
+mod validation_tests {
+    use super::*;
+
+    #[test]
+    fn validate_uniform_input_matching_defs_have_no_warnings() {
+        let defs = vec![("glow_texture".to_string(), UniformType::Sampler2d)];
+        let active = vec![("glow_texture".to_string(), UniformType::Sampler2d)];
+
+        assert_eq!(validate_uniform_input(&defs, &active), vec![]);
+    }
+
+    #[test]
+    fn validate_uniform_input_flags_inactive_uniform() {
+        let defs = vec![("glow_texture".to_string(), UniformType::Sampler2d)];
+
+        assert_eq!(
+            validate_uniform_input(&defs, &[]),
+            vec![UniformWarning::InactiveUniform {
+                name: "glow_texture".to_string()
+            }],
+        );
+    }
+
+    #[test]
+    fn validate_uniform_input_flags_type_mismatch() {
+        let defs = vec![("blend_mode".to_string(), UniformType::Float)];
+        let active = vec![("blend_mode".to_string(), UniformType::SignedInt)];
+
+        assert_eq!(
+            validate_uniform_input(&defs, &active),
+            vec![UniformWarning::TypeMismatch {
+                name: "blend_mode".to_string(),
+                rust_type: UniformType::Float,
+                program_type: UniformType::SignedInt,
+            }],
+        );
+    }
+
+    #[test]
+    fn validate_uniform_input_flags_missing_binding() {
+        let active = vec![("scene_texture".to_string(), UniformType::Sampler2d)];
+
+        assert_eq!(
+            validate_uniform_input(&[], &active),
+            vec![UniformWarning::MissingBinding {
+                name: "scene_texture".to_string()
+            }],
+        );
+    }
+
+    #[test]
+    fn validate_vertex_attribs_matching_defs_have_no_warnings() {
+        use glium::vertex::AttributeType;
+
+        let attribs = vec![("a_pos".to_string(), AttributeType::F32F32)];
+        let active = vec![("a_pos".to_string(), AttributeType::F32F32)];
+
+        assert_eq!(validate_vertex_attribs(&attribs, &active), vec![]);
+    }
+}
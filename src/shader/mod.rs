@@ -0,0 +1,10 @@
+pub mod core;
+pub mod input;
+pub mod stages;
+
+pub use core::{Core, InstancingMode};
+pub use input::{
+    DynamicUniforms, HasUniforms, InstanceInput, IntoUniformValue, StaticUniformType, ToUniforms,
+    UniformInput, UniformWarning, VertexAttribWarning,
+};
+pub use stages::Stages;
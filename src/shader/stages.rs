@@ -0,0 +1,77 @@
+use crate::CreationError;
+
+/// The optional non-vertex/fragment pipeline stages a `Core` may supply, in
+/// addition to its vertex and fragment sources.
+///
+/// `Core` (and the `build_program` path used for `blur_core`,
+/// `glow_map_core_transform` and friends) assumed a vertex+fragment pipeline
+/// only. A `Stages` lets a `Core` also carry a geometry shader and a
+/// tessellation control/evaluation pair, so components can emit primitives
+/// of their own, e.g. instanced wireframe expansion, hair/fur, or silhouette
+/// extrusion for a glow outline. `core_transform` implementations can inject
+/// or wrap these stages the same way they already transform the vertex and
+/// fragment sources.
+#[derive(Debug, Clone, Default)]
+pub struct Stages {
+    pub geometry: Option<String>,
+    pub tess_control: Option<String>,
+    pub tess_evaluation: Option<String>,
+}
+
+impl Stages {
+    pub fn none() -> Self {
+        Self::default()
+    }
+
+    pub fn with_geometry(mut self, source: impl Into<String>) -> Self {
+        self.geometry = Some(source.into());
+        self
+    }
+
+    pub fn with_tessellation(
+        mut self,
+        control: impl Into<String>,
+        evaluation: impl Into<String>,
+    ) -> Self {
+        self.tess_control = Some(control.into());
+        self.tess_evaluation = Some(evaluation.into());
+        self
+    }
+
+    /// A tessellation evaluation stage must be present whenever a control
+    /// stage is supplied, since GL links the pair as a unit.
+    pub fn validate(&self) -> Result<(), CreationError> {
+        if self.tess_control.is_some() && self.tess_evaluation.is_none() {
+            return Err(CreationError::Other(
+                "tessellation control stage given without a tessellation evaluation stage".into(),
+            ));
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn none_validates() {
+        assert!(Stages::none().validate().is_ok());
+    }
+
+    #[test]
+    fn tess_control_and_evaluation_together_validates() {
+        let stages = Stages::none().with_tessellation("control", "evaluation");
+
+        assert!(stages.validate().is_ok());
+    }
+
+    #[test]
+    fn tess_control_without_evaluation_is_rejected() {
+        let mut stages = Stages::none();
+        stages.tess_control = Some("control".to_string());
+
+        assert!(stages.validate().is_err());
+    }
+}